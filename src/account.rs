@@ -0,0 +1,77 @@
+use std::convert::TryInto;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    borsh::try_from_slice_unchecked,
+    program::invoke_signed,
+    program_error::ProgramError,
+    program_pack::IsInitialized,
+    pubkey::Pubkey,
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use crate::error::ReviewError;
+
+/// Deserializes `account`'s data as `T`, rejecting accounts not owned by `program_id`
+/// or not yet initialized, instead of panicking on a bad unwrap.
+pub fn get_account_data<T: BorshDeserialize + IsInitialized>(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<T, ProgramError> {
+    if account.owner != program_id {
+        return Err(ReviewError::IncorrectOwner.into());
+    }
+
+    let data = try_from_slice_unchecked::<T>(&account.data.borrow())?;
+
+    if !data.is_initialized() {
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    Ok(data)
+}
+
+/// Creates `pda_account` (signed by `seeds`, `account_len` bytes, rent-exempt) and serializes
+/// `data` into it; serialization fails if `account_len` is too small to hold `data`.
+pub fn create_and_serialize_pda_account<'a, T: BorshSerialize>(
+    payer: &AccountInfo<'a>,
+    pda_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+    account_len: usize,
+    data: &T,
+) -> Result<(), ProgramError> {
+    let rent_lamports = Rent::get()?.minimum_balance(account_len);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            pda_account.key,
+            rent_lamports,
+            account_len.try_into().unwrap(),
+            program_id,
+        ),
+        &[payer.clone(), pda_account.clone(), system_program.clone()],
+        &[seeds],
+    )?;
+
+    data.serialize(&mut &mut pda_account.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Reclaims `pda_account`'s rent into `recipient` and zeroes its data (which also clears
+/// any leading `is_initialized` flag) so the closed account can't be mistaken for a live one.
+pub fn close_pda_account<'a>(
+    recipient: &AccountInfo<'a>,
+    pda_account: &AccountInfo<'a>,
+) -> Result<(), ProgramError> {
+    **recipient.lamports.borrow_mut() += **pda_account.lamports.borrow();
+    **pda_account.lamports.borrow_mut() = 0;
+    pda_account.data.borrow_mut().fill(0);
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use solana_program::program_error::ProgramError;
+use thiserror::Error;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum ReviewError {
+    #[error("Account not initialized yet")]
+    UninitializedAccount,
+    #[error("PDA derived does not equal PDA passed in")]
+    InvalidPDA,
+    #[error("Rating greater than 5 or less than 1")]
+    InvalidRating,
+    #[error("Account does not have the correct program as owner")]
+    IncorrectOwner,
+    #[error("Account is already initialized")]
+    AlreadyInitialized,
+}
+
+impl From<ReviewError> for ProgramError {
+    fn from(e: ReviewError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,64 @@
+use solana_program::program_error::ProgramError;
+use borsh::BorshDeserialize;
+
+pub enum MovieInstruction {
+    AddMovieReview {
+        title: String,
+        rating: u8,
+        description: String,
+    },
+    UpdateMovieReview {
+        title: String,
+        rating: u8,
+        description: String,
+    },
+    AddComment {
+        comment: String,
+    },
+    CloseMovieReview,
+}
+
+#[derive(BorshDeserialize)]
+struct MovieReviewPayload {
+    title: String,
+    rating: u8,
+    description: String,
+}
+
+#[derive(BorshDeserialize)]
+struct MovieCommentPayload {
+    comment: String,
+}
+
+impl MovieInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&variant, rest) = input.split_first().ok_or(ProgramError::InvalidInstructionData)?;
+
+        Ok(match variant {
+            0 => {
+                let payload = MovieReviewPayload::try_from_slice(rest).unwrap();
+                Self::AddMovieReview {
+                    title: payload.title,
+                    rating: payload.rating,
+                    description: payload.description,
+                }
+            },
+            1 => {
+                let payload = MovieReviewPayload::try_from_slice(rest).unwrap();
+                Self::UpdateMovieReview {
+                    title: payload.title,
+                    rating: payload.rating,
+                    description: payload.description,
+                }
+            },
+            2 => {
+                let payload = MovieCommentPayload::try_from_slice(rest).unwrap();
+                Self::AddComment {
+                    comment: payload.comment,
+                }
+            },
+            3 => Self::CloseMovieReview,
+            _ => return Err(ProgramError::InvalidInstructionData)
+        })
+    }
+}
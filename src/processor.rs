@@ -5,7 +5,7 @@ use solana_program::{
     msg,
     system_instruction,
     sysvar::{rent::Rent, Sysvar},
-    program::invoke_signed,
+    program::{invoke, invoke_signed},
     borsh::try_from_slice_unchecked, 
     program_error::ProgramError, program_pack::IsInitialized,
 };
@@ -13,8 +13,9 @@ use solana_program::{
 use std::convert::TryInto;
 use borsh::BorshSerialize;
 
+use crate::account::{close_pda_account, create_and_serialize_pda_account, get_account_data};
 use crate::instruction::MovieInstruction;
-use crate::state::MovieAccountState;
+use crate::state::{MovieAccountState, MovieCommentCounter, MovieComment};
 use crate::error::ReviewError;
 
 pub fn process_instruction(
@@ -32,6 +33,12 @@ pub fn process_instruction(
         MovieInstruction::UpdateMovieReview { title, rating, description } => {
             // make call to update function that we'll define next
             update_movie_review(program_id, accounts, title, rating, description)
+        },
+        MovieInstruction::AddComment { comment } => {
+            add_comment(program_id, accounts, comment)
+        },
+        MovieInstruction::CloseMovieReview => {
+            close_movie_review(program_id, accounts)
         }
     }
 }
@@ -52,6 +59,7 @@ pub fn add_movie_review(
 
     let initializer = next_account_info(account_info_iter)?;
     let pda_account = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
     let system_program = next_account_info(account_info_iter)?;
 
     // ensure that the initializer of a review is also a signer on the transaction.
@@ -78,59 +86,63 @@ pub fn add_movie_review(
         return Err(ReviewError::InvalidRating.into())
     }
 
-    // let’s also check that the content of the review does not exceed the allocated space
-    let total_len: usize = 1 + 1 + (4 + title.len()) + (4 + description.len());
-    if total_len > 1000 {
-        msg!("Data length is larger than 1000 bytes");
-        return Err(ReviewError::InvalidDataLength.into())
+    // reject re-initialization of an already-created review instead of silently overwriting it
+    if pda_account.data_len() > 0 {
+        if let Ok(existing) = try_from_slice_unchecked::<MovieAccountState>(&pda_account.data.borrow()) {
+            if existing.is_initialized() {
+                msg!("Account already initialized");
+                return Err(ReviewError::AlreadyInitialized.into())
+            }
+        }
     }
 
-    let account_len = 1000;
-
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(account_len);
-
-    invoke_signed(
-        &system_instruction::create_account(
-            initializer.key,
-            pda_account.key, 
-            rent_lamports, 
-            account_len.try_into().unwrap(), 
-            program_id
-        ), 
-        &[
-            initializer.clone(),
-            pda_account.clone(),
-            system_program.clone(),
-            ], 
-        &[
-            &[
-                initializer.key.as_ref(),
-                title.as_bytes().as_ref(),
-                &[bump_seed]
-            ]
-        ]
+    let account_len: usize = 1 + 1 + (4 + title.len()) + (4 + description.len());
+
+    let account_data = MovieAccountState {
+        is_initialized: true,
+        rating,
+        title: title.clone(),
+        description,
+    };
+
+    create_and_serialize_pda_account(
+        initializer,
+        pda_account,
+        system_program,
+        program_id,
+        &[initializer.key.as_ref(), title.as_bytes().as_ref(), &[bump_seed]],
+        account_len,
+        &account_data,
     )?;
 
     msg!("PDA created: {}", pda);
 
-    msg!("unpacking state account");
-    let mut account_data = try_from_slice_unchecked::<MovieAccountState>(
-        &pda_account
-        .data
-        .borrow()
-    ).unwrap();
-
-    msg!("borrowed account data");
+    msg!("create comment counter");
+    let (counter_pda, counter_bump_seed) = Pubkey::find_program_address(
+        &[pda_account.key.as_ref(), b"comment"],
+        program_id
+    );
+    if counter_pda != *pda_counter.key {
+        msg!("Invalid seeds for comment counter PDA");
+        return Err(ProgramError::InvalidArgument)
+    }
 
-    account_data.title = title;
-    account_data.rating = rating;
-    account_data.description = description;
-    account_data.is_initialized = true;
+    let counter_data = MovieCommentCounter {
+        is_initialized: true,
+        counter: 0,
+    };
+
+    create_and_serialize_pda_account(
+        initializer,
+        pda_counter,
+        system_program,
+        program_id,
+        &[pda_account.key.as_ref(), b"comment", &[counter_bump_seed]],
+        1 + 8,
+        &counter_data,
+    )?;
 
-    msg!("serializing account");
-    account_data.serialize(&mut &mut pda_account.data.borrow_mut()[..])?;
-    msg!("state account serialized");
+    msg!("comment counter created");
 
     Ok(())
 }
@@ -150,11 +162,7 @@ pub fn update_movie_review(
     // Get accounts
     let initializer = next_account_info(account_info_iter)?;
     let pda_account = next_account_info(account_info_iter)?;
-		
-    // This is a good time to check that the pda_account.owner is the same as the program_id
-    if pda_account.owner != program_id {
-        return Err(ProgramError::IllegalOwner)
-    }
+    let system_program = next_account_info(account_info_iter)?;
 
     // check that the signer is the same as the initializer
     if !initializer.is_signer {
@@ -162,13 +170,9 @@ pub fn update_movie_review(
         return Err(ProgramError::MissingRequiredSignature)
     }
 
-    // unpack the data from the pda_account
+    // unpack the data from the pda_account, checking owner and initialization along the way
     msg!("unpacking state account");
-    let mut account_data = try_from_slice_unchecked::<MovieAccountState>(
-        &pda_account
-        .data
-        .borrow()
-    ).unwrap();
+    let mut account_data = get_account_data::<MovieAccountState>(pda_account, program_id)?;
     msg!("borrowed account data");
 
     // Derive PDA and check that it matches client
@@ -179,20 +183,25 @@ pub fn update_movie_review(
         return Err(ReviewError::InvalidPDA.into())
     }
 
-    if !account_data.is_initialized() {
-        msg!("Account is not initialized");
-        return Err(ReviewError::UninitializedAccount.into());
-    }
-
     if rating > 5 || rating < 1 {
         msg!("Rating cannot be higher than 5");
         return Err(ReviewError::InvalidRating.into())
     }
 
-    let total_len: usize = 1 + 1 + (4 + account_data.title.len()) + (4 + description.len());
-    if total_len > 1000 {
-        msg!("Data length is larger than 1000 bytes");
-        return Err(ReviewError::InvalidDataLength.into())
+    let new_len: usize = 1 + 1 + (4 + account_data.title.len()) + (4 + description.len());
+
+    // grow or shrink the account to fit the new description exactly, topping up rent when growing
+    if new_len != pda_account.data_len() {
+        let rent = Rent::get()?;
+        let new_minimum_balance = rent.minimum_balance(new_len);
+        let lamports_diff = new_minimum_balance.saturating_sub(pda_account.lamports());
+        if lamports_diff > 0 {
+            invoke(
+                &system_instruction::transfer(initializer.key, pda_account.key, lamports_diff),
+                &[initializer.clone(), pda_account.clone(), system_program.clone()],
+            )?;
+        }
+        pda_account.realloc(new_len, false)?;
     }
 
     // update the account info and serialize it to account
@@ -205,5 +214,150 @@ pub fn update_movie_review(
         .borrow_mut()[..]
     )?;
 
+    Ok(())
+}
+
+pub fn add_comment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    comment: String,
+) -> ProgramResult {
+    msg!("Adding Comment...");
+    msg!("Comment: {}", comment);
+
+    let account_info_iter = &mut accounts.iter();
+
+    let commenter = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+    let pda_comment = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !commenter.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature)
+    }
+
+    let (counter_pda, _counter_bump_seed) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), b"comment"],
+        program_id
+    );
+    if counter_pda != *pda_counter.key {
+        msg!("Invalid seeds for counter PDA");
+        return Err(ReviewError::InvalidPDA.into())
+    }
+
+    msg!("unpacking comment counter");
+    let mut counter_data = get_account_data::<MovieCommentCounter>(pda_counter, program_id)?;
+    msg!("borrowed counter account data");
+
+    let account_len: usize = 1 + 32 + 32 + (4 + comment.len()) + 8;
+
+    let rent = Rent::get()?;
+    let rent_lamports = rent.minimum_balance(account_len);
+
+    let (comment_pda, comment_bump_seed) = Pubkey::find_program_address(
+        &[
+            pda_review.key.as_ref(),
+            counter_data.counter.to_be_bytes().as_ref(),
+        ],
+        program_id
+    );
+    if comment_pda != *pda_comment.key {
+        msg!("Invalid seeds for comment PDA");
+        return Err(ReviewError::InvalidPDA.into())
+    }
+
+    invoke_signed(
+        &system_instruction::create_account(
+            commenter.key,
+            pda_comment.key,
+            rent_lamports,
+            account_len.try_into().unwrap(),
+            program_id
+        ),
+        &[
+            commenter.clone(),
+            pda_comment.clone(),
+            system_program.clone(),
+        ],
+        &[
+            &[
+                pda_review.key.as_ref(),
+                counter_data.counter.to_be_bytes().as_ref(),
+                &[comment_bump_seed]
+            ]
+        ]
+    )?;
+
+    msg!("Comment PDA created: {}", comment_pda);
+
+    let mut comment_data = try_from_slice_unchecked::<MovieComment>(
+        &pda_comment
+        .data
+        .borrow()
+    ).map_err(|_| ProgramError::InvalidAccountData)?;
+
+    comment_data.is_initialized = true;
+    comment_data.review = *pda_review.key;
+    comment_data.commenter = *commenter.key;
+    comment_data.comment = comment;
+    comment_data.count = counter_data.counter;
+
+    msg!("serializing comment account");
+    comment_data.serialize(&mut &mut pda_comment.data.borrow_mut()[..])?;
+    msg!("comment account serialized");
+
+    msg!("updating comment counter");
+    counter_data.counter += 1;
+    counter_data.serialize(&mut &mut pda_counter.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn close_movie_review(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    msg!("Closing movie review...");
+
+    let account_info_iter = &mut accounts.iter();
+
+    let initializer = next_account_info(account_info_iter)?;
+    let pda_account = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+
+    if !initializer.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature)
+    }
+
+    let account_data = get_account_data::<MovieAccountState>(pda_account, program_id)?;
+
+    // Derive PDA and check that the initializer matches the review it's trying to close
+    let (pda, _bump_seed) = Pubkey::find_program_address(
+        &[initializer.key.as_ref(), account_data.title.as_bytes().as_ref()],
+        program_id
+    );
+    if pda != *pda_account.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into())
+    }
+
+    // Derive the comment counter PDA so no orphaned rent is left behind
+    let (counter_pda, _counter_bump_seed) = Pubkey::find_program_address(
+        &[pda_account.key.as_ref(), b"comment"],
+        program_id
+    );
+    if counter_pda != *pda_counter.key {
+        msg!("Invalid seeds for comment counter PDA");
+        return Err(ReviewError::InvalidPDA.into())
+    }
+
+    close_pda_account(initializer, pda_counter)?;
+    close_pda_account(initializer, pda_account)?;
+
+    msg!("review and comment counter closed");
+
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,50 @@
+use borsh::{BorshSerialize, BorshDeserialize};
+use solana_program::program_pack::{IsInitialized, Sealed};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MovieAccountState {
+    pub is_initialized: bool,
+    pub rating: u8,
+    pub title: String,
+    pub description: String,
+}
+
+impl Sealed for MovieAccountState {}
+
+impl IsInitialized for MovieAccountState {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MovieCommentCounter {
+    pub is_initialized: bool,
+    pub counter: u64,
+}
+
+impl Sealed for MovieCommentCounter {}
+
+impl IsInitialized for MovieCommentCounter {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MovieComment {
+    pub is_initialized: bool,
+    pub review: Pubkey,
+    pub commenter: Pubkey,
+    pub comment: String,
+    pub count: u64,
+}
+
+impl Sealed for MovieComment {}
+
+impl IsInitialized for MovieComment {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}